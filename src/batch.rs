@@ -1,8 +1,12 @@
 use anyhow::Result;
+use image::DynamicImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use serde_json;
-use crate::client::{ocr_via_server, OcrResult};
+use crate::client::{ocr_via_server_cached, HttpTimeouts, OcrResult};
+use crate::pdf;
+use crate::templates::OcrOutput;
 
 /// Batch result for one image
 #[derive(Debug, serde::Serialize)]
@@ -21,6 +25,8 @@ pub fn process_batch(
     gpu: bool,
     use_server: bool,
     server_url: &str,
+    cache_dir: &Path,
+    no_cache: bool,
     run_ocr_subprocess: impl Fn(&str, &[String], bool, i32) -> Result<Vec<OcrResult>>,
 ) -> Vec<BatchResult> {
     let pb = ProgressBar::new(files.len() as u64);
@@ -36,8 +42,25 @@ pub fn process_batch(
     for file in files {
         pb.set_message(format!("Processing {}", file));
 
+        if is_pdf(file) {
+            let page_results = process_pdf_file(
+                file,
+                languages,
+                detail,
+                gpu,
+                use_server,
+                server_url,
+                cache_dir,
+                no_cache,
+                &run_ocr_subprocess,
+            );
+            results.extend(page_results);
+            pb.inc(1);
+            continue;
+        }
+
         let result = if use_server {
-            match ocr_via_server(file, languages, detail, gpu, server_url) {
+            match ocr_via_server_cached(file, languages, detail, gpu, server_url, HttpTimeouts::default(), cache_dir, no_cache) {
                 Ok(ocr_results) => BatchResult {
                     file: file.clone(),
                     success: true,
@@ -75,3 +98,317 @@ pub fn process_batch(
     pb.finish_with_message("Batch processing complete");
     results
 }
+
+/// True for inputs that should go through the PDF hybrid text/OCR path instead of direct OCR
+fn is_pdf(file: &str) -> bool {
+    Path::new(file)
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false)
+}
+
+static TEMP_PAGE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Process a single PDF into one `BatchResult` per page, using the hybrid text/OCR strategy:
+/// pages with an embedded text layer are used as-is, pages without one are rasterized to a
+/// temp image and run through the existing OCR path (`run_ocr_subprocess` or the server)
+fn process_pdf_file(
+    file: &str,
+    languages: &[String],
+    detail: i32,
+    gpu: bool,
+    use_server: bool,
+    server_url: &str,
+    cache_dir: &Path,
+    no_cache: bool,
+    run_ocr_subprocess: &impl Fn(&str, &[String], bool, i32) -> Result<Vec<OcrResult>>,
+) -> Vec<BatchResult> {
+    let pdf_path = Path::new(file);
+    let config = pdf::PdfRasterConfig::default();
+
+    let ocr_rasterized_page = |image: &DynamicImage| -> Result<Vec<OcrResult>> {
+        let suffix = TEMP_PAGE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let tmp_path = std::env::temp_dir().join(format!("rustocr_page_{}_{}.png", std::process::id(), suffix));
+        image.save(&tmp_path)?;
+
+        let tmp_str = tmp_path.to_string_lossy().to_string();
+        let result = if use_server {
+            ocr_via_server_cached(&tmp_str, languages, detail, gpu, server_url, HttpTimeouts::default(), cache_dir, no_cache)
+        } else {
+            run_ocr_subprocess(&tmp_str, languages, gpu, detail)
+        };
+
+        let _ = std::fs::remove_file(&tmp_path);
+        result
+    };
+
+    match pdf::process_pdf(pdf_path, &config, ocr_rasterized_page) {
+        Ok(pages) => pages
+            .into_iter()
+            .map(|page| BatchResult {
+                file: format!("{}#page{}", file, page.page_number),
+                success: true,
+                results: Some(vec![OcrResult {
+                    bbox: vec![],
+                    text: page.output.text,
+                    confidence: page.output.confidence as f64,
+                }]),
+                error: None,
+            })
+            .collect(),
+        Err(e) => vec![BatchResult {
+            file: file.to_string(),
+            success: false,
+            results: None,
+            error: Some(e.to_string()),
+        }],
+    }
+}
+
+/// Per-file CER/WER against a ground-truth reference
+#[derive(Debug, serde::Serialize)]
+pub struct EvalResult {
+    pub file: String,
+    pub cer: f64,
+    pub wer: f64,
+}
+
+/// Aggregate evaluation across an entire batch
+#[derive(Debug, serde::Serialize)]
+pub struct BatchEvaluation {
+    pub per_file: Vec<EvalResult>,
+    pub aggregate_cer: f64,
+    pub aggregate_wer: f64,
+}
+
+/// Classic Levenshtein edit distance over two rolling rows
+fn edit_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let len_b = b.len();
+    let mut prev: Vec<usize> = (0..=len_b).collect();
+    let mut cur = vec![0usize; len_b + 1];
+
+    for (i, a_item) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for j in 0..len_b {
+            let cost = if *a_item != b[j] { 1 } else { 0 };
+            cur[j + 1] = (prev[j + 1] + 1).min(cur[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    prev[len_b]
+}
+
+/// Character Error Rate: edit_distance(chars) / ref_char_count, clamped to avoid div-by-zero
+pub fn char_error_rate(hypothesis: &str, reference: &str) -> f64 {
+    let hyp_chars: Vec<char> = hypothesis.chars().collect();
+    let ref_chars: Vec<char> = reference.chars().collect();
+
+    if ref_chars.is_empty() {
+        return if hyp_chars.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    edit_distance(&hyp_chars, &ref_chars) as f64 / ref_chars.len() as f64
+}
+
+/// Word Error Rate: edit_distance(whitespace-tokenized words) / ref_word_count, clamped
+pub fn word_error_rate(hypothesis: &str, reference: &str) -> f64 {
+    let hyp_words: Vec<&str> = hypothesis.split_whitespace().collect();
+    let ref_words: Vec<&str> = reference.split_whitespace().collect();
+
+    if ref_words.is_empty() {
+        return if hyp_words.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    edit_distance(&hyp_words, &ref_words) as f64 / ref_words.len() as f64
+}
+
+/// Locate the ground-truth sidecar file for an image (e.g. `image.jpg` -> `image.txt`)
+///
+/// A PDF page's `BatchResult.file` is the pseudo-path `"{file}#page{N}"` (see `process_pdf_file`),
+/// which isn't a real filesystem path: naively calling `.with_extension("txt")` on it would
+/// collapse every page of the same PDF onto the single sidecar `"{stem}.txt"`. Page references
+/// instead live at the page-numbered sidecar `"{stem}.page{N}.txt"` next to the source PDF.
+fn reference_path_for(image_path: &str) -> std::path::PathBuf {
+    if let Some((base, page)) = image_path.split_once("#page") {
+        let base_path = Path::new(base);
+        let stem = base_path.file_stem().unwrap_or_default();
+        let mut file_name = stem.to_os_string();
+        file_name.push(format!(".page{}.txt", page));
+        return base_path.with_file_name(file_name);
+    }
+
+    Path::new(image_path).with_extension("txt")
+}
+
+/// Load the reference text for an image, if a sidecar `.txt` file exists next to it
+pub fn load_reference_text(image_path: &str) -> Option<String> {
+    let ref_path = reference_path_for(image_path);
+    std::fs::read_to_string(&ref_path).ok()
+}
+
+/// Evaluate a batch's recognized text against per-file reference text sidecars
+///
+/// Files without a reference are skipped. The aggregate CER/WER are micro-averaged
+/// (summed edit distances over summed reference lengths) rather than a mean of
+/// per-file rates, so longer documents are weighted proportionally.
+pub fn evaluate_batch(results: &[BatchResult]) -> BatchEvaluation {
+    let mut per_file = Vec::new();
+    let mut total_char_dist = 0usize;
+    let mut total_char_count = 0usize;
+    let mut total_word_dist = 0usize;
+    let mut total_word_count = 0usize;
+
+    for result in results {
+        let Some(ocr_results) = &result.results else {
+            continue;
+        };
+        let Some(reference) = load_reference_text(&result.file) else {
+            continue;
+        };
+
+        let hypothesis = ocr_results
+            .iter()
+            .map(|r| r.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let hyp_chars: Vec<char> = hypothesis.chars().collect();
+        let ref_chars: Vec<char> = reference.chars().collect();
+        let hyp_words: Vec<&str> = hypothesis.split_whitespace().collect();
+        let ref_words: Vec<&str> = reference.split_whitespace().collect();
+
+        let char_dist = edit_distance(&hyp_chars, &ref_chars);
+        let word_dist = edit_distance(&hyp_words, &ref_words);
+
+        total_char_dist += char_dist;
+        total_char_count += ref_chars.len();
+        total_word_dist += word_dist;
+        total_word_count += ref_words.len();
+
+        per_file.push(EvalResult {
+            file: result.file.clone(),
+            cer: char_error_rate(&hypothesis, &reference),
+            wer: word_error_rate(&hypothesis, &reference),
+        });
+    }
+
+    let aggregate_cer = if total_char_count == 0 {
+        0.0
+    } else {
+        total_char_dist as f64 / total_char_count as f64
+    };
+    let aggregate_wer = if total_word_count == 0 {
+        0.0
+    } else {
+        total_word_dist as f64 / total_word_count as f64
+    };
+
+    BatchEvaluation {
+        per_file,
+        aggregate_cer,
+        aggregate_wer,
+    }
+}
+
+/// Flatten `BatchResult`s into `OcrOutput` rows for the existing formatters, stamping each
+/// row's `cer`/`wer` from the matching per-file entry in `evaluation`, if one was computed
+///
+/// Failed files (no `results`) are dropped rather than emitted as an empty row, since the
+/// existing formatters have no field for a per-file error message.
+pub fn to_ocr_outputs(results: &[BatchResult], evaluation: Option<&BatchEvaluation>) -> Vec<OcrOutput> {
+    results
+        .iter()
+        .filter_map(|result| {
+            let ocr_results = result.results.as_ref()?;
+            let eval = evaluation.and_then(|e| e.per_file.iter().find(|f| f.file == result.file));
+
+            let text = ocr_results
+                .iter()
+                .map(|r| r.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let confidence = if ocr_results.is_empty() {
+                0.0
+            } else {
+                (ocr_results.iter().map(|r| r.confidence).sum::<f64>() / ocr_results.len() as f64) as f32
+            };
+            let bbox = ocr_results.first().map(|r| r.bbox.clone());
+
+            Some(OcrOutput {
+                file: result.file.clone(),
+                text,
+                confidence,
+                bbox,
+                cer: eval.map(|e| e.cer),
+                wer: eval.map(|e| e.wer),
+                source: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_error_rate_exact_match() {
+        assert_eq!(char_error_rate("hello world", "hello world"), 0.0);
+    }
+
+    #[test]
+    fn test_char_error_rate_empty_reference() {
+        assert_eq!(char_error_rate("", ""), 0.0);
+        assert_eq!(char_error_rate("hello", ""), 1.0);
+    }
+
+    #[test]
+    fn test_reference_path_for_pdf_page_uses_page_numbered_sidecar() {
+        assert_eq!(
+            reference_path_for("docs/report.pdf#page3"),
+            std::path::PathBuf::from("docs/report.page3.txt")
+        );
+        assert_eq!(
+            reference_path_for("image.jpg"),
+            std::path::PathBuf::from("image.txt")
+        );
+    }
+
+    #[test]
+    fn test_word_error_rate_single_substitution() {
+        // One of three words differs
+        let wer = word_error_rate("the cat sat", "the dog sat");
+        assert!((wer - (1.0 / 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_ocr_outputs_stamps_cer_wer_and_drops_failures() {
+        let results = vec![
+            BatchResult {
+                file: "a.jpg".to_string(),
+                success: true,
+                results: Some(vec![OcrResult { bbox: vec![], text: "hi".to_string(), confidence: 0.8 }]),
+                error: None,
+            },
+            BatchResult {
+                file: "b.jpg".to_string(),
+                success: false,
+                results: None,
+                error: Some("boom".to_string()),
+            },
+        ];
+        let evaluation = BatchEvaluation {
+            per_file: vec![EvalResult { file: "a.jpg".to_string(), cer: 0.1, wer: 0.2 }],
+            aggregate_cer: 0.1,
+            aggregate_wer: 0.2,
+        };
+
+        let outputs = to_ocr_outputs(&results, Some(&evaluation));
+
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].file, "a.jpg");
+        assert_eq!(outputs[0].cer, Some(0.1));
+        assert_eq!(outputs[0].wer, Some(0.2));
+    }
+}
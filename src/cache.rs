@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::client::OcrResult;
+
+/// Content-addressed cache for OCR results
+///
+/// The cache key is a BLAKE3 digest over the raw image bytes plus a canonical encoding
+/// of every request parameter that affects the output (sorted languages, detail, gpu).
+/// Because the digest covers everything that can change the result, entries never go
+/// stale and nothing ever needs to be evicted.
+
+const CACHE_DIR_ENV: &str = "RUSTOCR_CACHE_DIR";
+
+/// Resolve the cache directory: explicit override > `RUSTOCR_CACHE_DIR` > `~/.cache/rustocr`
+pub fn cache_dir(override_dir: Option<&str>) -> PathBuf {
+    if let Some(dir) = override_dir {
+        return PathBuf::from(dir);
+    }
+
+    if let Ok(dir) = std::env::var(CACHE_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("rustocr")
+}
+
+/// Compute the content-addressed cache key for an OCR request
+pub fn cache_key(image_bytes: &[u8], languages: &[String], detail: i32, gpu: bool) -> String {
+    let mut sorted_languages = languages.to_vec();
+    sorted_languages.sort();
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(image_bytes);
+    hasher.update(b"\0lang=");
+    hasher.update(sorted_languages.join(",").as_bytes());
+    hasher.update(b"\0detail=");
+    hasher.update(detail.to_string().as_bytes());
+    hasher.update(b"\0gpu=");
+    hasher.update(&[gpu as u8]);
+
+    hasher.finalize().to_hex().to_string()
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{}.json", key))
+}
+
+/// Look up a cached result; `None` on any miss, including a corrupt or unreadable entry
+pub fn lookup(dir: &Path, key: &str) -> Option<Vec<OcrResult>> {
+    let contents = std::fs::read_to_string(entry_path(dir, key)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Atomically store a result: write to a temp file in the same directory, then rename
+pub fn store(dir: &Path, key: &str, results: &[OcrResult]) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+
+    let final_path = entry_path(dir, key);
+    let tmp_path = dir.join(format!("{}.json.tmp", key));
+
+    let contents =
+        serde_json::to_string(results).context("Failed to serialize OCR results for caching")?;
+    std::fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed to write cache temp file: {}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, &final_path)
+        .with_context(|| format!("Failed to finalize cache entry: {}", final_path.display()))?;
+
+    Ok(())
+}
+
+/// Remove every cached entry (the `--cache-clear` maintenance command)
+pub fn clear(dir: &Path) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read cache directory: {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove cache entry: {}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run `compute` through the cache: looks up first (unless `no_cache`), and stores the
+/// result of a miss before returning it. Shared by `ocr_via_server_cached` (client.rs) and
+/// `run_ocr_subprocess_cached` (main.rs) so both OCR paths skip re-running on identical inputs.
+pub fn with_cache(
+    image_path: &str,
+    languages: &[String],
+    detail: i32,
+    gpu: bool,
+    cache_dir: &Path,
+    no_cache: bool,
+    compute: impl FnOnce() -> Result<Vec<OcrResult>>,
+) -> Result<Vec<OcrResult>> {
+    if no_cache {
+        return compute();
+    }
+
+    let image_bytes = std::fs::read(image_path)
+        .with_context(|| format!("Failed to read image file for caching: {}", image_path))?;
+    let key = cache_key(&image_bytes, languages, detail, gpu);
+
+    if let Some(cached) = lookup(cache_dir, &key) {
+        eprintln!("Cache hit for {}", image_path);
+        return Ok(cached);
+    }
+
+    let results = compute()?;
+    if let Err(e) = store(cache_dir, &key, &results) {
+        eprintln!("Warning: failed to write cache entry: {}", e);
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_stable_regardless_of_language_order() {
+        let a = cache_key(b"image-bytes", &["en".to_string(), "ch_sim".to_string()], 1, false);
+        let b = cache_key(b"image-bytes", &["ch_sim".to_string(), "en".to_string()], 1, false);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_cache_key_changes_with_params() {
+        let a = cache_key(b"image-bytes", &["en".to_string()], 1, false);
+        let b = cache_key(b"image-bytes", &["en".to_string()], 0, false);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_store_and_lookup_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("rustocr_cache_test_{}", std::process::id()));
+        let key = "testkey";
+        let results = vec![OcrResult {
+            bbox: vec![],
+            text: "hello".to_string(),
+            confidence: 0.9,
+        }];
+
+        store(&dir, key, &results).unwrap();
+        let loaded = lookup(&dir, key).expect("cache entry should be present");
+        assert_eq!(loaded[0].text, "hello");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
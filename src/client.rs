@@ -1,6 +1,96 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+
+use crate::cache;
+
+/// Connect/read timeouts for the shared HTTP client
+#[derive(Debug, Clone, Copy)]
+pub struct HttpTimeouts {
+    pub connect: Duration,
+    pub read: Duration,
+}
+
+impl Default for HttpTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            read: Duration::from_secs(60),
+        }
+    }
+}
+
+static HTTP_CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Install a Ctrl+C handler that cancels any in-flight request started through this module
+///
+/// Once the flag is set, every caller waiting in `send_with_cancel` returns an error within
+/// one 200ms poll, and `ocr_via_server_stream` stops at its next line. Requests already handed
+/// to `send()` keep running on their background thread until they complete or time out.
+pub fn install_ctrlc_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        eprintln!("\nReceived Ctrl+C, cancelling in-flight requests...");
+        CANCELLED.store(true, Ordering::SeqCst);
+    })
+    .context("Failed to install Ctrl+C handler")
+}
+
+fn is_cancelled() -> bool {
+    CANCELLED.load(Ordering::SeqCst)
+}
+
+/// Build (on first use) or reuse the shared rustls-backed client with connection pooling
+///
+/// The first caller's timeouts win for the lifetime of the process; later calls with
+/// different timeouts silently reuse the already-built client.
+fn http_client(timeouts: HttpTimeouts) -> Result<&'static reqwest::blocking::Client> {
+    if let Some(client) = HTTP_CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .use_rustls_tls()
+        .connect_timeout(timeouts.connect)
+        .timeout(timeouts.read)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    Ok(HTTP_CLIENT.get_or_init(|| client))
+}
+
+/// Send a request on a background thread and poll for cancellation, so a Ctrl+C during a
+/// hung request returns to the caller promptly instead of blocking on the OS socket
+///
+/// This issues exactly one real HTTP request — unlike a short-timeout retry loop, which would
+/// fire a brand-new request every poll interval while the server keeps working on the first
+/// one. The tradeoff is that the background thread itself stays blocked in `send()` until the
+/// client's own read timeout elapses on its own if cancelled; that's an acceptable cost for
+/// not duplicating expensive OCR work server-side.
+fn send_with_cancel(request: reqwest::blocking::RequestBuilder) -> Result<reqwest::blocking::Response> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(request.send());
+    });
+
+    loop {
+        if is_cancelled() {
+            anyhow::bail!("Request cancelled by user (Ctrl+C)");
+        }
+
+        match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(result) => return result.context("HTTP request failed"),
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("Request thread terminated unexpectedly");
+            }
+        }
+    }
+}
 
 /// Represents the result of OCR detection for a single text region
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,12 +131,13 @@ pub fn ocr_via_server(
     detail: i32,
     gpu: bool,
     server_url: &str,
+    timeouts: HttpTimeouts,
 ) -> Result<Vec<OcrResult>> {
     // Read and encode image
     let image_data = std::fs::read(image_path)
         .context(format!("Failed to read image file: {}", image_path))?;
     let base64_image = base64::encode(&image_data);
-    
+
     // Build request
     let request = OcrRequest {
         image: base64_image,
@@ -54,23 +145,20 @@ pub fn ocr_via_server(
         detail,
         gpu,
     };
-    
+
     // Send HTTP request
-    let client = reqwest::blocking::Client::new();
+    let client = http_client(timeouts)?;
     let url = format!("{}/api/v1/ocr", server_url);
-    
-    let response = client
-        .post(&url)
-        .json(&request)
-        .send()
+
+    let response = send_with_cancel(client.post(&url).json(&request))
         .context(format!("Failed to connect to server at {}", server_url))?;
-    
+
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response.text().unwrap_or_else(|_| "Unknown error".to_string());
         anyhow::bail!("Server returned error {}: {}", status, error_text);
     }
-    
+
     // Parse response
     let ocr_response: OcrResponse = response
         .json()
@@ -83,12 +171,123 @@ pub fn ocr_via_server(
     Ok(ocr_response.results)
 }
 
+/// Perform OCR using the server, served from the content-addressed cache on a repeat request
+pub fn ocr_via_server_cached(
+    image_path: &str,
+    languages: &[String],
+    detail: i32,
+    gpu: bool,
+    server_url: &str,
+    timeouts: HttpTimeouts,
+    cache_dir: &Path,
+    no_cache: bool,
+) -> Result<Vec<OcrResult>> {
+    cache::with_cache(image_path, languages, detail, gpu, cache_dir, no_cache, || {
+        ocr_via_server(image_path, languages, detail, gpu, server_url, timeouts)
+    })
+}
+
+/// Request body for the streaming NDJSON endpoint: one or more images in a single submission
+#[derive(Debug, Serialize)]
+struct StreamRequest {
+    images: Vec<String>,
+    languages: Vec<String>,
+    detail: i32,
+    gpu: bool,
+}
+
+/// Trailing frame closing out a streaming response, carrying request bookkeeping/timings
+#[derive(Debug, Deserialize)]
+pub struct StreamSummary {
+    pub request_id: String,
+    pub processing_time_ms: f64,
+    pub model_load_time_ms: f64,
+}
+
+/// A single line of the NDJSON stream: either a detected region or the trailing summary.
+/// `text` has no default, so the summary frame (which carries no `text`) does not match
+/// `Region` and falls through to `Summary`; a region frame that omits `text` matches
+/// neither variant and fails to parse.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StreamFrame {
+    Region(OcrResult),
+    Summary(StreamSummary),
+}
+
+/// Perform OCR over one or more images via the server's streaming NDJSON endpoint
+///
+/// Unlike `ocr_via_server`, this does not buffer the whole response: each detected region
+/// is parsed and handed to `on_region` as soon as its line arrives, so callers (e.g. a
+/// progress bar) can react per-region on large, region-dense pages instead of waiting for
+/// the full batch. Returns the trailing summary frame once the stream closes.
+pub fn ocr_via_server_stream(
+    image_paths: &[String],
+    languages: &[String],
+    detail: i32,
+    gpu: bool,
+    server_url: &str,
+    timeouts: HttpTimeouts,
+    mut on_region: impl FnMut(OcrResult),
+) -> Result<StreamSummary> {
+    use std::io::{BufRead, BufReader};
+
+    let images = image_paths
+        .iter()
+        .map(|path| {
+            std::fs::read(path)
+                .map(|bytes| base64::encode(&bytes))
+                .with_context(|| format!("Failed to read image file: {}", path))
+        })
+        .collect::<Result<Vec<String>>>()?;
+
+    let request = StreamRequest {
+        images,
+        languages: languages.to_vec(),
+        detail,
+        gpu,
+    };
+
+    let client = http_client(timeouts)?;
+    let url = format!("{}/api/v1/ocr/stream", server_url);
+
+    let response = send_with_cancel(client.post(&url).json(&request))
+        .context(format!("Failed to connect to server at {}", server_url))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Server returned error {}: {}", status, error_text);
+    }
+
+    let mut summary = None;
+    for line in BufReader::new(response).lines() {
+        if is_cancelled() {
+            anyhow::bail!("Streaming response cancelled by user (Ctrl+C)");
+        }
+
+        let line = line.context("Failed to read a line from the streaming response")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<StreamFrame>(&line)
+            .with_context(|| format!("Failed to parse NDJSON frame: {}", line))?
+        {
+            StreamFrame::Region(result) => on_region(result),
+            StreamFrame::Summary(s) => summary = Some(s),
+        }
+    }
+
+    summary.context("Streaming response ended without a trailing summary frame")
+}
+
 /// Check if server is healthy
-pub fn check_server_health(server_url: &str) -> Result<bool> {
-    let client = reqwest::blocking::Client::new();
+pub fn check_server_health(server_url: &str, timeouts: HttpTimeouts) -> Result<bool> {
+    let client = http_client(timeouts)?;
     let url = format!("{}/api/v1/health", server_url);
-    
-    match client.get(&url).send() {
+
+    match send_with_cancel(client.get(&url)) {
         Ok(response) => Ok(response.status().is_success()),
         Err(_) => Ok(false),
     }
@@ -9,6 +9,7 @@ pub struct Config {
     pub default: Option<DefaultConfig>,
     pub server: Option<ServerConfig>,
     pub batch: Option<BatchConfig>,
+    pub normalize: Option<NormalizeConfig>,
     pub profiles: Option<HashMap<String, ProfileConfig>>,
 }
 
@@ -44,6 +45,24 @@ pub struct ProfileConfig {
     pub gpu: Option<bool>,
     pub output: Option<String>,
     pub detail: Option<i32>,
+    pub rules: Option<Vec<NormalizeRuleConfig>>,
+}
+
+/// A single text-normalization rule as loaded from `config.toml`
+///
+/// `exact` selects a cheap literal substitution instead of compiling `pattern` as a regex.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NormalizeRuleConfig {
+    pub pattern: String,
+    pub replace: String,
+    #[serde(default)]
+    pub exact: bool,
+}
+
+/// Default, top-level `[normalize]` rule set applied when a profile doesn't define its own
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NormalizeConfig {
+    pub rules: Option<Vec<NormalizeRuleConfig>>,
 }
 
 impl Config {
@@ -114,6 +133,13 @@ impl Config {
                 ));
             }
 
+            if let Some(normalize) = config.normalize {
+                result.normalize = Some(Self::merge_normalize(
+                    result.normalize.take(),
+                    normalize,
+                ));
+            }
+
             if let Some(profiles) = config.profiles {
                 let mut merged_profiles = result.profiles.take().unwrap_or_default();
                 merged_profiles.extend(profiles);
@@ -188,6 +214,16 @@ impl Config {
         result
     }
 
+    fn merge_normalize(base: Option<NormalizeConfig>, override_cfg: NormalizeConfig) -> NormalizeConfig {
+        let mut result = base.unwrap_or(NormalizeConfig { rules: None });
+
+        if let Some(rules) = override_cfg.rules {
+            result.rules = Some(rules);
+        }
+
+        result
+    }
+
     /// Create default config file template
     pub fn create_default() -> String {
         r#"# RustOCR Configuration File
@@ -209,6 +245,14 @@ auto_start = false
 output_dir = "./results"
 continue_on_error = true
 
+# Text normalization applied to recognized text before formatting.
+# `exact = true` does a cheap literal replacement instead of compiling `pattern` as a regex.
+[normalize]
+rules = [
+    { pattern = "-\\n", replace = "" },
+    { pattern = "\\s+", replace = " " },
+]
+
 # Example profiles
 [profiles.chinese]
 languages = ["ch_sim", "en"]
@@ -4,9 +4,15 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+mod batch;
+mod cache;
 mod client;
+mod config;
+mod parallel;
+mod pdf;
 mod server;
-use client::{check_server_health, ocr_via_server, OcrResult};
+mod templates;
+use client::{check_server_health, ocr_via_server_cached, HttpTimeouts, OcrResult};
 
 /// RustOCR - A fast Rust CLI for EasyOCR with 80+ language support
 #[derive(Parser, Debug)]
@@ -59,6 +65,66 @@ struct Args {
     /// Check server status
     #[arg(long, conflicts_with_all = ["use_server", "server", "server_stop"])]
     server_status: bool,
+
+    /// Directory for the OCR result cache (default: ~/.cache/rustocr, or $RUSTOCR_CACHE_DIR)
+    #[arg(long)]
+    cache_dir: Option<String>,
+
+    /// Bypass the OCR result cache for this run
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Remove all cached OCR results and exit
+    #[arg(long, conflicts_with_all = ["use_server", "server", "server_stop", "server_status"])]
+    cache_clear: bool,
+
+    /// Connection timeout in seconds for server requests
+    #[arg(long, default_value = "10")]
+    connect_timeout: u64,
+
+    /// Read timeout in seconds for server requests
+    #[arg(long, default_value = "60")]
+    read_timeout: u64,
+
+    /// Batch-process every image path listed (one per line) in this file instead of --input
+    #[arg(long, conflicts_with = "input")]
+    batch_list: Option<String>,
+
+    /// After a batch run, score recognized text against per-file `<image>.txt` reference
+    /// sidecars and include aggregate/per-file CER and WER in the output
+    ///
+    /// Not compatible with --stream: streaming collapses every file's regions into a single
+    /// combined `BatchResult`, so there's no per-file path left to look a reference up against.
+    #[arg(long, requires = "batch_list", conflicts_with = "stream")]
+    eval: bool,
+
+    /// Named config profile (from `[profiles.<name>]`) whose normalize rules are applied to
+    /// batch output text, overriding the top-level `[normalize]` default
+    #[arg(long, requires = "batch_list")]
+    profile: Option<String>,
+
+    /// Extra OCR server URLs (comma-separated) to dispatch a --batch-list run across as a
+    /// worker pool instead of sending every file to the single --server-url
+    #[arg(long, value_delimiter = ',', requires = "batch_list", conflicts_with = "resilient")]
+    server_pool: Vec<String>,
+
+    /// Process a --batch-list run with a bounded worker pool and per-item retries instead of
+    /// the default sequential walk
+    #[arg(long, requires = "batch_list", conflicts_with = "server_pool")]
+    resilient: bool,
+
+    /// Worker count for --resilient (default: number of CPUs)
+    #[arg(long, requires = "resilient")]
+    max_in_flight: Option<usize>,
+
+    /// Retry attempts per item for --resilient
+    #[arg(long, default_value = "2", requires = "resilient")]
+    max_retries: u32,
+
+    /// Submit an entire --batch-list run to the server's streaming NDJSON endpoint as one
+    /// request instead of one request per file
+    #[arg(long, requires_all = ["batch_list", "use_server"], conflicts_with_all = ["server_pool", "resilient", "eval"])]
+    stream: bool,
 }
 
 fn get_bridge_script_path() -> Result<PathBuf> {
@@ -133,9 +199,285 @@ fn run_ocr_subprocess(
     Ok(results)
 }
 
+fn run_ocr_subprocess_cached(
+    image_path: &str,
+    languages: &[String],
+    gpu: bool,
+    detail: i32,
+    cache_dir: &Path,
+    no_cache: bool,
+) -> Result<Vec<OcrResult>> {
+    cache::with_cache(image_path, languages, detail, gpu, cache_dir, no_cache, || {
+        run_ocr_subprocess(image_path, languages, gpu, detail)
+    })
+}
+
+/// Dispatch a --batch-list run across `args.server_pool` plus `args.server_url`, converting
+/// `parallel::distributed`'s per-image results back into `BatchResult`s so the rest of
+/// `run_batch` (eval, `to_ocr_outputs`, formatting) doesn't need to know which strategy ran
+fn run_batch_distributed(files: &[String], args: &Args, timeouts: HttpTimeouts) -> Vec<batch::BatchResult> {
+    let mut server_urls = args.server_pool.clone();
+    server_urls.push(args.server_url.clone());
+
+    let images: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+    let config = parallel::DistributedConfig {
+        server_urls,
+        languages: args.languages.clone(),
+        detail: args.detail,
+        gpu: args.gpu,
+        timeouts,
+    };
+
+    parallel::distributed(images, config)
+        .into_iter()
+        .map(|(path, result)| match result {
+            Ok(ocr_results) => batch::BatchResult {
+                file: path.to_string_lossy().into_owned(),
+                success: true,
+                results: Some(ocr_results),
+                error: None,
+            },
+            Err(e) => batch::BatchResult {
+                file: path.to_string_lossy().into_owned(),
+                success: false,
+                results: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// Run a --batch-list run through `parallel::process_images_resilient`'s bounded worker pool
+/// with per-item retries, converting its `ItemReport`s back into `BatchResult`s
+fn run_batch_resilient(
+    files: &[String],
+    args: &Args,
+    timeouts: HttpTimeouts,
+    cache_dir: &Path,
+    no_cache: bool,
+) -> Vec<batch::BatchResult> {
+    let images: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+    let config = parallel::ResilientConfig {
+        max_in_flight: args.max_in_flight.unwrap_or_else(num_cpus::get),
+        max_retries: args.max_retries,
+        ..parallel::ResilientConfig::default()
+    };
+
+    let use_server = args.use_server;
+    let server_url = args.server_url.clone();
+    let languages = args.languages.clone();
+    let detail = args.detail;
+    let gpu = args.gpu;
+    let cache_dir = cache_dir.to_path_buf();
+
+    let processor = move |path: &PathBuf| -> Result<String> {
+        let path_str = path.to_string_lossy().into_owned();
+        let ocr_results = if use_server {
+            ocr_via_server_cached(&path_str, &languages, detail, gpu, &server_url, timeouts, &cache_dir, no_cache)?
+        } else {
+            run_ocr_subprocess_cached(&path_str, &languages, gpu, detail, &cache_dir, no_cache)?
+        };
+        Ok(ocr_results.iter().map(|r| r.text.as_str()).collect::<Vec<_>>().join("\n"))
+    };
+
+    parallel::process_images_resilient(images, config, processor)
+        .into_iter()
+        .map(|report| match report.result {
+            Ok(text) => batch::BatchResult {
+                file: report.path.to_string_lossy().into_owned(),
+                success: true,
+                results: Some(vec![OcrResult { bbox: vec![], text, confidence: 1.0 }]),
+                error: None,
+            },
+            Err(e) => batch::BatchResult {
+                file: report.path.to_string_lossy().into_owned(),
+                success: false,
+                results: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// Submit an entire --batch-list run to the server's streaming NDJSON endpoint as a single
+/// request. Regions come back for the whole batch at once rather than per file, so (unlike
+/// the other strategies) this produces one combined `BatchResult` rather than one per file.
+fn run_batch_streaming(files: &[String], args: &Args, timeouts: HttpTimeouts) -> Result<Vec<batch::BatchResult>> {
+    let regions = parallel::process_images_streaming_with_progress(
+        files,
+        &args.languages,
+        args.detail,
+        args.gpu,
+        &args.server_url,
+        timeouts,
+    )?;
+
+    Ok(vec![batch::BatchResult {
+        file: format!("{} file(s) (streamed)", files.len()),
+        success: true,
+        results: Some(regions),
+        error: None,
+    }])
+}
+
+/// Batch-process every path listed in `args.batch_list`, optionally scoring the results
+/// against per-file reference sidecars, and print them through the existing `OcrOutput`
+/// formatters instead of the single-image output path
+fn run_batch(args: &Args, timeouts: HttpTimeouts) -> Result<()> {
+    let list_path = args
+        .batch_list
+        .as_ref()
+        .context("run_batch called without --batch-list")?;
+    let contents = std::fs::read_to_string(list_path)
+        .with_context(|| format!("Failed to read batch list file: {}", list_path))?;
+    let files: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if files.is_empty() {
+        anyhow::bail!("Batch list file {} contained no image paths", list_path);
+    }
+
+    eprintln!("Batch processing {} file(s)", files.len());
+
+    let cache_dir = cache::cache_dir(args.cache_dir.as_deref());
+    let no_cache = args.no_cache;
+
+    if args.use_server && !check_server_health(&args.server_url, timeouts)? {
+        anyhow::bail!("Server not available at {}", args.server_url);
+    }
+
+    let results = if !args.server_pool.is_empty() {
+        run_batch_distributed(&files, args, timeouts)
+    } else if args.resilient {
+        run_batch_resilient(&files, args, timeouts, &cache_dir, no_cache)
+    } else if args.stream {
+        run_batch_streaming(&files, args, timeouts)?
+    } else {
+        batch::process_batch(
+            &files,
+            &args.languages,
+            args.detail,
+            args.gpu,
+            args.use_server,
+            &args.server_url,
+            &cache_dir,
+            no_cache,
+            |path, languages, gpu, detail| {
+                run_ocr_subprocess_cached(path, languages, gpu, detail, &cache_dir, no_cache)
+            },
+        )
+    };
+
+    let evaluation = if args.eval {
+        let evaluation = batch::evaluate_batch(&results);
+        eprintln!(
+            "Aggregate CER: {:.4}, Aggregate WER: {:.4}",
+            evaluation.aggregate_cer, evaluation.aggregate_wer
+        );
+        Some(evaluation)
+    } else {
+        None
+    };
+
+    let outputs = batch::to_ocr_outputs(&results, evaluation.as_ref());
+
+    let pipeline = templates::resolve_pipeline(&config::Config::load()?, args.profile.as_deref())?;
+
+    let formatted = match args.output.as_str() {
+        "json" => templates::format_json(&outputs, pipeline.as_ref())?,
+        "csv" => templates::format_csv(&outputs, pipeline.as_ref())?,
+        "xml" => templates::format_xml(&outputs, pipeline.as_ref())?,
+        "markdown" => templates::format_markdown(&outputs, pipeline.as_ref())?,
+        "text" => outputs
+            .iter()
+            .map(|o| match &pipeline {
+                Some(p) => p.apply(&o.text),
+                None => o.text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "detailed" => serde_json::to_string_pretty(&results).context("Failed to serialize batch results")?,
+        _ => anyhow::bail!("Invalid output format. Use: json, csv, xml, markdown, text, or detailed"),
+    };
+
+    println!("{}", formatted);
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    eprintln!("Batch completed: {}/{} succeeded", results.len() - failed, results.len());
+
+    Ok(())
+}
+
+/// True for inputs that should go through the PDF hybrid text/OCR path instead of direct OCR
+fn is_pdf_input(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("pdf"))
+        .unwrap_or(false)
+}
+
+/// Run a single PDF through `pdf::process_pdf`, rasterizing pages without an embedded text
+/// layer to a temp image and recognizing them through the same subprocess/server path as a
+/// regular image, so the existing output formats (json/text/detailed) work unchanged
+fn run_pdf(input: &str, args: &Args, timeouts: HttpTimeouts, cache_dir: &Path) -> Result<Vec<OcrResult>> {
+    let pdf_path = Path::new(input);
+    let config = pdf::PdfRasterConfig::default();
+
+    let pages = pdf::process_pdf(pdf_path, &config, |image| {
+        let tmp_path = std::env::temp_dir().join(format!("rustocr_pdfpage_{}.png", std::process::id()));
+        image.save(&tmp_path)?;
+        let tmp_str = tmp_path.to_string_lossy().to_string();
+
+        let result = if args.use_server {
+            ocr_via_server_cached(
+                &tmp_str,
+                &args.languages,
+                args.detail,
+                args.gpu,
+                &args.server_url,
+                timeouts,
+                cache_dir,
+                args.no_cache,
+            )
+        } else {
+            run_ocr_subprocess_cached(&tmp_str, &args.languages, args.gpu, args.detail, cache_dir, args.no_cache)
+        };
+
+        let _ = std::fs::remove_file(&tmp_path);
+        result
+    })?;
+
+    Ok(pages
+        .into_iter()
+        .map(|page| OcrResult {
+            bbox: vec![],
+            text: page.output.text,
+            confidence: page.output.confidence as f64,
+        })
+        .collect())
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    client::install_ctrlc_handler()?;
+    let timeouts = HttpTimeouts {
+        connect: std::time::Duration::from_secs(args.connect_timeout),
+        read: std::time::Duration::from_secs(args.read_timeout),
+    };
+
+    // Handle cache maintenance
+    if args.cache_clear {
+        let dir = cache::cache_dir(args.cache_dir.as_deref());
+        cache::clear(&dir)?;
+        eprintln!("Cache cleared: {}", dir.display());
+        return Ok(());
+    }
+
     // Handle server management commands
     if args.server_stop {
         return server::stop_server();
@@ -172,6 +514,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.batch_list.is_some() {
+        return run_batch(&args, timeouts);
+    }
+
     // Validate input is provided for OCR operations
     let input = args.input.as_ref()
         .ok_or_else(|| anyhow::anyhow!("--input is required for OCR operations"))?;
@@ -191,27 +537,35 @@ fn main() -> Result<()> {
     eprintln!("GPU enabled: {}", args.gpu);
     eprintln!("Processing image: {}", input);
     
+    let cache_dir = cache::cache_dir(args.cache_dir.as_deref());
+
     // Choose mode
-    let results = if args.use_server {
+    let results = if is_pdf_input(input) {
+        eprintln!("Detected PDF input, using hybrid embedded-text/OCR extraction");
+        run_pdf(input, &args, timeouts, &cache_dir)?
+    } else if args.use_server {
         eprintln!("Using server mode at: {}", args.server_url);
-        
+
         // Check server health
-        if !check_server_health(&args.server_url)? {
+        if !check_server_health(&args.server_url, timeouts)? {
             eprintln!("Warning: Server at {} is not responding", args.server_url);
             eprintln!("Make sure the server is running: python3 easyocr_server.py");
             anyhow::bail!("Server not available");
         }
-        
-        ocr_via_server(
+
+        ocr_via_server_cached(
             input,
             &args.languages,
             args.detail,
             args.gpu,
-            &args.server_url
+            &args.server_url,
+            timeouts,
+            &cache_dir,
+            args.no_cache,
         )?
     } else {
         eprintln!("Using subprocess mode");
-        run_ocr_subprocess(input, &args.languages, args.gpu, args.detail)?
+        run_ocr_subprocess_cached(input, &args.languages, args.gpu, args.detail, &cache_dir, args.no_cache)?
     };
 
     // Output results based on format
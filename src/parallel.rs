@@ -1,10 +1,15 @@
 use anyhow::Result;
 use rayon::prelude::*;
+use std::collections::VecDeque;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, Once};
+use std::time::Duration;
+
+use crate::client::{check_server_health, ocr_via_server, ocr_via_server_stream, HttpTimeouts, OcrResult};
 
 /// Parallel processing module for batch OCR operations
-/// 
+///
 /// Uses rayon for CPU parallelization to process multiple images concurrently
 
 #[derive(Debug)]
@@ -22,6 +27,22 @@ impl Default for ParallelConfig {
     }
 }
 
+static THREAD_POOL_INIT: Once = Once::new();
+
+/// Install `config.num_threads` as rayon's global thread pool, once per process
+///
+/// `process_images_parallel` and `process_images_parallel_with_progress` both used to build a
+/// fresh `ThreadPoolBuilder` on every call without ever installing it, so the build was wasted
+/// work and `par_iter` ran on rayon's default global pool regardless. The first caller's thread
+/// count wins for the process lifetime, matching `http_client`'s first-caller-wins convention.
+fn ensure_thread_pool(num_threads: usize) {
+    THREAD_POOL_INIT.call_once(|| {
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build_global();
+    });
+}
+
 /// Process images in parallel using rayon
 pub fn process_images_parallel<F>(
     images: Vec<PathBuf>,
@@ -31,11 +52,7 @@ pub fn process_images_parallel<F>(
 where
     F: Fn(&PathBuf) -> Result<String> + Send + Sync,
 {
-    // Configure rayon thread pool
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(config.num_threads)
-        .build()
-        .expect("Failed to build thread pool");
+    ensure_thread_pool(config.num_threads);
 
     // Process images in parallel
     images
@@ -54,7 +71,9 @@ where
     F: Fn(&PathBuf) -> Result<String> + Send + Sync,
 {
     use indicatif::{ProgressBar, ProgressStyle};
-    
+
+    ensure_thread_pool(config.num_threads);
+
     let pb = Arc::new(Mutex::new(ProgressBar::new(images.len() as u64)));
     {
         let pb = pb.lock().unwrap();
@@ -79,6 +98,331 @@ where
     results
 }
 
+/// Submit a batch to the server's streaming NDJSON endpoint, advancing the progress bar
+/// once per detected region instead of once per image
+///
+/// Large, region-dense pages otherwise stall the bar for as long as the whole image takes
+/// to process; streaming gives it partial feedback as regions arrive.
+pub fn process_images_streaming_with_progress(
+    image_paths: &[String],
+    languages: &[String],
+    detail: i32,
+    gpu: bool,
+    server_url: &str,
+    timeouts: HttpTimeouts,
+) -> Result<Vec<OcrResult>> {
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner} {msg}")
+            .expect("Invalid progress bar template"),
+    );
+    pb.set_message("Waiting for first region...");
+
+    let mut regions: Vec<OcrResult> = Vec::new();
+    let summary = ocr_via_server_stream(
+        image_paths,
+        languages,
+        detail,
+        gpu,
+        server_url,
+        timeouts,
+        |result| {
+            regions.push(result);
+            pb.set_message(format!("{} region(s) received", regions.len()));
+            pb.tick();
+        },
+    )?;
+
+    pb.finish_with_message(format!(
+        "Streaming complete: {} region(s) in {:.2}ms",
+        regions.len(),
+        summary.processing_time_ms
+    ));
+
+    Ok(regions)
+}
+
+/// Configuration for a distributed dispatch run across a pool of OCR servers
+#[derive(Debug, Clone)]
+pub struct DistributedConfig {
+    pub server_urls: Vec<String>,
+    pub languages: Vec<String>,
+    pub detail: i32,
+    pub gpu: bool,
+    pub timeouts: HttpTimeouts,
+}
+
+/// One server in a distributed pool, tracked for health and current load
+struct PoolServer {
+    url: String,
+    alive: AtomicBool,
+    in_flight: AtomicUsize,
+}
+
+/// Cap on total attempts for a single image across the pool, so one permanently broken image (e.g.
+/// corrupt or unsupported input) can't loop forever bouncing between otherwise-healthy servers
+const MAX_ITEM_ATTEMPTS: u32 = 3;
+
+/// Dispatch a batch of images across a pool of OCR servers instead of one local host
+///
+/// Each live server gets a worker thread pulling from a single shared queue, so faster or
+/// less-loaded servers naturally drain more of it than a naive round-robin would give them.
+/// When `ocr_via_server` fails for an item, the server is re-checked with `check_server_health`
+/// before being marked down — a per-item OCR error (an unsupported or corrupt image) doesn't
+/// take a perfectly healthy server out of the pool, only an actual connection-level failure
+/// does. Either way the failed item is re-queued for another server to pick up, up to
+/// `MAX_ITEM_ATTEMPTS` attempts, rather than being recorded as a permanent failure on its first
+/// error. Results are returned in the same order as `images`; an image that never got
+/// dispatched at all (e.g. every server went down before it was popped) comes back as an error
+/// rather than being silently dropped.
+pub fn distributed(
+    images: Vec<PathBuf>,
+    config: DistributedConfig,
+) -> Vec<(PathBuf, Result<Vec<OcrResult>>)> {
+    let servers: Vec<Arc<PoolServer>> = config
+        .server_urls
+        .iter()
+        .filter_map(|url| match check_server_health(url, config.timeouts) {
+            Ok(true) => Some(Arc::new(PoolServer {
+                url: url.clone(),
+                alive: AtomicBool::new(true),
+                in_flight: AtomicUsize::new(0),
+            })),
+            _ => {
+                eprintln!("Server {} failed health check, excluding from pool", url);
+                None
+            }
+        })
+        .collect();
+
+    if servers.is_empty() {
+        return images
+            .into_iter()
+            .map(|path| (path, Err(anyhow::anyhow!("No healthy OCR servers available"))))
+            .collect();
+    }
+
+    let fallback_paths = images.clone();
+    let work_queue: Arc<Mutex<VecDeque<(usize, PathBuf, u32)>>> = Arc::new(Mutex::new(
+        images.into_iter().enumerate().map(|(index, path)| (index, path, 0)).collect(),
+    ));
+    let (tx, rx) = mpsc::channel::<(usize, Result<Vec<OcrResult>>)>();
+
+    let mut handles = Vec::with_capacity(servers.len());
+    for server in &servers {
+        let server = Arc::clone(server);
+        let work_queue = Arc::clone(&work_queue);
+        let tx = tx.clone();
+        let languages = config.languages.clone();
+        let detail = config.detail;
+        let gpu = config.gpu;
+        let timeouts = config.timeouts;
+
+        handles.push(std::thread::spawn(move || loop {
+            if !server.alive.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let Some((index, path, attempts)) = work_queue.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            server.in_flight.fetch_add(1, Ordering::SeqCst);
+            let path_str = path.to_string_lossy().into_owned();
+            let result = ocr_via_server(&path_str, &languages, detail, gpu, &server.url, timeouts);
+            server.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            match result {
+                Ok(ocr_results) => {
+                    if tx.send((index, Ok(ocr_results))).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    // A failed item alone doesn't prove the server is down — confirm with a
+                    // real health check before taking it out of the pool.
+                    if !check_server_health(&server.url, timeouts).unwrap_or(false) {
+                        server.alive.store(false, Ordering::SeqCst);
+                    }
+
+                    let next_attempts = attempts + 1;
+                    if next_attempts >= MAX_ITEM_ATTEMPTS {
+                        if tx.send((index, Err(e))).is_err() {
+                            break;
+                        }
+                    } else {
+                        work_queue.lock().unwrap().push_back((index, path, next_attempts));
+                    }
+                }
+            }
+        }));
+    }
+    drop(tx);
+
+    let mut ordered: Vec<Option<Result<Vec<OcrResult>>>> = (0..fallback_paths.len()).map(|_| None).collect();
+    for (index, result) in rx {
+        ordered[index] = Some(result);
+    }
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    ordered
+        .into_iter()
+        .zip(fallback_paths)
+        .map(|(result, path)| {
+            let result = result
+                .unwrap_or_else(|| Err(anyhow::anyhow!("Image was never dispatched (all servers went down)")));
+            (path, result)
+        })
+        .collect()
+}
+
+/// Bounded-concurrency and retry settings for `process_images_resilient`
+#[derive(Debug, Clone)]
+pub struct ResilientConfig {
+    /// Maximum number of images processed at once, independent of rayon's thread count
+    pub max_in_flight: usize,
+    /// Additional attempts after the first failure, before giving up on an item
+    pub max_retries: u32,
+    /// Delay before the first retry; each subsequent retry doubles it
+    pub base_backoff: Duration,
+}
+
+impl Default for ResilientConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: num_cpus::get(),
+            max_retries: 2,
+            base_backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Outcome of a single item after `process_images_resilient`'s retry loop
+#[derive(Debug)]
+pub struct ItemReport {
+    pub path: PathBuf,
+    pub result: Result<String>,
+    /// Total attempts made, including the first; 1 means it succeeded without a retry
+    pub attempts: u32,
+}
+
+/// Aggregate view of a resilient run's item reports, grouped by how each one finished
+#[derive(Debug)]
+pub struct ExecutionSummary {
+    pub succeeded: Vec<PathBuf>,
+    pub retried_then_succeeded: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Group item reports into succeeded / retried-then-succeeded / permanently-failed
+pub fn summarize(reports: &[ItemReport]) -> ExecutionSummary {
+    let mut succeeded = Vec::new();
+    let mut retried_then_succeeded = Vec::new();
+    let mut failed = Vec::new();
+
+    for report in reports {
+        match &report.result {
+            Ok(_) if report.attempts <= 1 => succeeded.push(report.path.clone()),
+            Ok(_) => retried_then_succeeded.push(report.path.clone()),
+            Err(e) => failed.push((report.path.clone(), e.to_string())),
+        }
+    }
+
+    ExecutionSummary {
+        succeeded,
+        retried_then_succeeded,
+        failed,
+    }
+}
+
+/// Process images with a bounded pool of workers, retrying transient failures with backoff
+///
+/// A fixed-size pool of `config.max_in_flight` worker threads pull from a shared queue, so
+/// concurrency is capped independent of rayon's thread count (unlike `process_images_parallel`,
+/// which hands every image to rayon at once). Each failure is retried up to
+/// `config.max_retries` times with exponentially increasing backoff before the item is recorded
+/// as permanently failed. The progress bar ticks once per finished item (succeeded or
+/// permanently failed), not once per attempt, so its position is the completed+failed count.
+/// Reports come back in completion order, not submission order; pass them to `summarize` for a
+/// succeeded/retried/failed breakdown.
+pub fn process_images_resilient<F>(
+    images: Vec<PathBuf>,
+    config: ResilientConfig,
+    processor: F,
+) -> Vec<ItemReport>
+where
+    F: Fn(&PathBuf) -> Result<String> + Send + Sync,
+{
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let pb = Arc::new(Mutex::new(ProgressBar::new(images.len() as u64)));
+    {
+        let pb = pb.lock().unwrap();
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} {msg}")
+                .expect("Invalid progress bar template")
+                .progress_chars("█▓▒░  "),
+        );
+    }
+
+    let processor = Arc::new(processor);
+    let work_queue: Arc<Mutex<VecDeque<PathBuf>>> = Arc::new(Mutex::new(images.into_iter().collect()));
+    let (tx, rx) = mpsc::channel::<ItemReport>();
+    let num_workers = config.max_in_flight.max(1);
+
+    let mut handles = Vec::with_capacity(num_workers);
+    for _ in 0..num_workers {
+        let work_queue = Arc::clone(&work_queue);
+        let processor = Arc::clone(&processor);
+        let pb = Arc::clone(&pb);
+        let tx = tx.clone();
+        let max_retries = config.max_retries;
+        let base_backoff = config.base_backoff;
+
+        handles.push(std::thread::spawn(move || loop {
+            let Some(path) = work_queue.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            let mut attempts = 0u32;
+            let result = loop {
+                attempts += 1;
+                let attempt_result = processor(&path);
+
+                match attempt_result {
+                    Ok(value) => break Ok(value),
+                    Err(_) if attempts <= max_retries => {
+                        std::thread::sleep(base_backoff * 2u32.pow(attempts - 1));
+                    }
+                    Err(e) => break Err(e),
+                }
+            };
+            pb.lock().unwrap().inc(1);
+
+            if tx.send(ItemReport { path, result, attempts }).is_err() {
+                break;
+            }
+        }));
+    }
+    drop(tx);
+
+    let reports: Vec<ItemReport> = rx.into_iter().collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    pb.lock().unwrap().finish_with_message("Resilient batch processing complete");
+    reports
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +433,68 @@ mod tests {
         assert!(config.num_threads > 0);
         assert_eq!(config.chunk_size, 10);
     }
+
+    #[test]
+    fn test_resilient_retries_then_succeeds() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let config = ResilientConfig {
+            max_in_flight: 1,
+            max_retries: 2,
+            base_backoff: Duration::from_millis(1),
+        };
+
+        let attempts_clone = Arc::clone(&attempts);
+        let reports = process_images_resilient(vec![PathBuf::from("flaky.png")], config, move |_| {
+            let n = attempts_clone.fetch_add(1, Ordering::SeqCst);
+            if n < 1 {
+                Err(anyhow::anyhow!("transient failure"))
+            } else {
+                Ok("ok".to_string())
+            }
+        });
+
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].result.is_ok());
+        assert_eq!(reports[0].attempts, 2);
+
+        let summary = summarize(&reports);
+        assert_eq!(summary.retried_then_succeeded.len(), 1);
+        assert!(summary.succeeded.is_empty());
+        assert!(summary.failed.is_empty());
+    }
+
+    #[test]
+    fn test_resilient_reports_permanent_failure() {
+        let config = ResilientConfig {
+            max_in_flight: 1,
+            max_retries: 1,
+            base_backoff: Duration::from_millis(1),
+        };
+
+        let reports = process_images_resilient(vec![PathBuf::from("broken.png")], config, |_| {
+            Err(anyhow::anyhow!("permanent failure"))
+        });
+
+        let summary = summarize(&reports);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].1, "permanent failure");
+    }
+
+    #[test]
+    #[ignore] // requires a reachable (or deliberately unreachable) network address
+    fn test_distributed_with_no_healthy_servers_returns_errors() {
+        let config = DistributedConfig {
+            server_urls: vec!["http://127.0.0.1:1".to_string()],
+            languages: vec!["en".to_string()],
+            detail: 1,
+            gpu: false,
+            timeouts: HttpTimeouts::default(),
+        };
+
+        let images = vec![PathBuf::from("a.png"), PathBuf::from("b.png")];
+        let results = distributed(images, config);
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, r)| r.is_err()));
+    }
 }
@@ -3,10 +3,31 @@ use std::path::Path;
 use image::DynamicImage;
 use pdf_extract::extract_text;
 
+use crate::client::OcrResult;
+use crate::templates::OcrOutput;
+
 /// PDF processing module for RustOCR
-/// 
+///
 /// Provides functionality to extract images and text from PDF files
-/// for OCR processing.
+/// for OCR processing, preferring the embedded text layer over OCR
+/// wherever a page already carries one.
+
+/// Where a page's recognized text came from
+pub const SOURCE_EMBEDDED: &str = "embedded";
+pub const SOURCE_OCR: &str = "ocr";
+
+/// Rasterization settings for `PdfHandle::rasterize`
+#[derive(Debug, Clone)]
+pub struct PdfRasterConfig {
+    /// Render resolution in dots per inch; higher improves OCR accuracy at the cost of memory
+    pub dpi: u32,
+}
+
+impl Default for PdfRasterConfig {
+    fn default() -> Self {
+        Self { dpi: 200 }
+    }
+}
 
 /// Extract text directly from PDF (if available)
 pub fn extract_pdf_text(pdf_path: &Path) -> Result<String> {
@@ -24,34 +45,209 @@ pub fn has_extractable_text(pdf_path: &Path) -> bool {
     }
 }
 
-/// Convert PDF pages to images for OCR
-/// Returns vector of images, one per page
-pub fn pdf_to_images(pdf_path: &Path) -> Result<Vec<DynamicImage>> {
-    // Note: Full PDF rendering requires poppler/mupdf
-    // This is a placeholder for the architecture
-    // In production, you'd use pdf-render or call external tools
-    
-    eprintln!("PDF to image conversion requires additional dependencies.");
-    eprintln!("Consider using: pdf2image, poppler-utils, or mupdf");
-    eprintln!("For now, please convert PDFs to images externally.");
-    
-    Err(anyhow::anyhow!("PDF rendering not yet implemented. Please convert PDF to images first."))
+#[cfg(feature = "pdfium")]
+mod render {
+    use super::*;
+    use pdfium_render::prelude::*;
+
+    /// A PDF opened once and kept alive for as long as the caller needs it, so page count,
+    /// embedded text, and rasterization all read from the same parsed document instead of
+    /// each reloading and reparsing the file from disk
+    pub struct PdfHandle {
+        document: PdfDocument<'static>,
+    }
+
+    impl PdfHandle {
+        pub fn open(pdf_path: &Path) -> Result<Self> {
+            // `Pdfium` is leaked so `document` (which borrows from it) can outlive this
+            // function and live as long as the handle; one leaked binding per opened PDF is
+            // a worthwhile trade against reloading and reparsing the whole file per page.
+            let pdfium: &'static Pdfium = Box::leak(Box::new(
+                Pdfium::bind_to_system_library()
+                    .map(Pdfium::new)
+                    .context("Failed to bind to a system pdfium library")?,
+            ));
+            let document = pdfium
+                .load_pdf_from_file(pdf_path, None)
+                .context("Failed to load PDF document")?;
+
+            Ok(Self { document })
+        }
+
+        /// Number of pages in the opened document
+        pub fn page_count(&self) -> usize {
+            self.document.pages().len() as usize
+        }
+
+        /// Per-page embedded text, if pdfium can pull a text layer for that page
+        pub fn page_text(&self, page_index: usize) -> Result<String> {
+            let page = self
+                .document
+                .pages()
+                .get(page_index as u16)
+                .context("Page index out of range")?;
+            Ok(page.text()?.all())
+        }
+
+        /// Rasterize every page to a `DynamicImage` at `config.dpi`
+        pub fn rasterize(&self, config: &PdfRasterConfig) -> Result<Vec<DynamicImage>> {
+            let mut images = Vec::with_capacity(self.document.pages().len() as usize);
+            for page in self.document.pages().iter() {
+                let width = (page.width().value * config.dpi as f32 / 72.0) as i32;
+                let height = (page.height().value * config.dpi as f32 / 72.0) as i32;
+
+                let bitmap = page
+                    .render_with_config(
+                        &PdfRenderConfig::new()
+                            .set_target_size(width, height),
+                    )
+                    .context("Failed to rasterize PDF page")?;
+
+                images.push(bitmap.as_image());
+            }
+
+            Ok(images)
+        }
+    }
+}
+
+#[cfg(not(feature = "pdfium"))]
+mod render {
+    use super::*;
+
+    /// Stand-in for the pdfium-backed handle when the `pdfium` feature is disabled: reports a
+    /// single page and falls back to whole-document text extraction for it
+    pub struct PdfHandle {
+        path: std::path::PathBuf,
+    }
+
+    impl PdfHandle {
+        pub fn open(pdf_path: &Path) -> Result<Self> {
+            Ok(Self { path: pdf_path.to_path_buf() })
+        }
+
+        /// Without the `pdfium` feature we cannot inspect the page tree, so this falls back
+        /// to reporting a single page.
+        pub fn page_count(&self) -> usize {
+            1
+        }
+
+        pub fn page_text(&self, page_index: usize) -> Result<String> {
+            if page_index == 0 {
+                extract_pdf_text(&self.path)
+            } else {
+                Ok(String::new())
+            }
+        }
+
+        /// Real rasterization requires the `pdfium` feature (pdfium-render); without it there
+        /// is no bundled renderer and this remains a placeholder.
+        pub fn rasterize(&self, _config: &PdfRasterConfig) -> Result<Vec<DynamicImage>> {
+            eprintln!("PDF rasterization requires the `pdfium` feature (pdfium-render).");
+            eprintln!("Rebuild with --features pdfium, or convert PDFs to images externally.");
+
+            Err(anyhow::anyhow!(
+                "PDF rendering not available in this build. Enable the `pdfium` feature or convert PDF to images first."
+            ))
+        }
+    }
+}
+
+pub use render::PdfHandle;
+
+/// One page's worth of hybrid text/OCR processing
+pub struct PdfPageResult {
+    pub page_number: usize,
+    pub output: OcrOutput,
 }
 
-/// Get PDF page count
-pub fn get_page_count(pdf_path: &Path) -> Result<usize> {
-    // This would require pdf-rs or similar
-    // Placeholder implementation
-    Ok(1)
+/// Process a PDF with a hybrid text/OCR strategy: pages with an embedded text layer are
+/// returned as-is, pages without one are rasterized and run through `ocr`
+pub fn process_pdf(
+    pdf_path: &Path,
+    config: &PdfRasterConfig,
+    ocr: impl Fn(&DynamicImage) -> Result<Vec<OcrResult>>,
+) -> Result<Vec<PdfPageResult>> {
+    let handle = PdfHandle::open(pdf_path)?;
+    let page_count = handle.page_count();
+    let file_name = pdf_path.display().to_string();
+    let mut pages = Vec::with_capacity(page_count);
+
+    // Only rasterize if at least one page lacks an embedded text layer, and only once.
+    let mut images: Option<Vec<DynamicImage>> = None;
+
+    for page_index in 0..page_count {
+        let embedded_text = handle.page_text(page_index).unwrap_or_default();
+
+        let output = if !embedded_text.trim().is_empty() {
+            OcrOutput {
+                file: file_name.clone(),
+                text: embedded_text,
+                confidence: 1.0,
+                bbox: None,
+                cer: None,
+                wer: None,
+                source: Some(SOURCE_EMBEDDED.to_string()),
+            }
+        } else {
+            if images.is_none() {
+                images = Some(handle.rasterize(config)?);
+            }
+            let page_image = images
+                .as_ref()
+                .unwrap()
+                .get(page_index)
+                .context("Rasterized page count did not match page_count")?;
+            let results = ocr(page_image)?;
+            let text = results
+                .iter()
+                .map(|r| r.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            let confidence = if results.is_empty() {
+                0.0
+            } else {
+                (results.iter().map(|r| r.confidence).sum::<f64>() / results.len() as f64) as f32
+            };
+
+            OcrOutput {
+                file: file_name.clone(),
+                text,
+                confidence,
+                bbox: None,
+                cer: None,
+                wer: None,
+                source: Some(SOURCE_OCR.to_string()),
+            }
+        };
+
+        pages.push(PdfPageResult {
+            page_number: page_index + 1,
+            output,
+        });
+    }
+
+    Ok(pages)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    #[test]
+    fn test_render_config_default_dpi() {
+        assert_eq!(PdfRasterConfig::default().dpi, 200);
+    }
+
     #[test]
-    fn test_pdf_module_exists() {
-        // Basic test to verify module compiles
-        assert!(true);
+    fn test_pdf_handle_without_feature_errors_on_rasterize() {
+        // Without the `pdfium` feature there is no renderer, so this should
+        // surface a clear error rather than silently returning no pages.
+        #[cfg(not(feature = "pdfium"))]
+        {
+            let handle = PdfHandle::open(Path::new("nonexistent.pdf")).unwrap();
+            let result = handle.rasterize(&PdfRasterConfig::default());
+            assert!(result.is_err());
+        }
     }
 }
@@ -1,9 +1,12 @@
 use anyhow::Result;
+use regex::Regex;
 use serde::Serialize;
 use std::path::Path;
 
+use crate::config::{Config, NormalizeRuleConfig};
+
 /// Output template system for flexible result formatting
-/// 
+///
 /// Supports multiple output formats: CSV, JSON, XML, Markdown
 
 #[derive(Debug, Serialize)]
@@ -12,16 +15,94 @@ pub struct OcrOutput {
     pub text: String,
     pub confidence: f32,
     pub bbox: Option<Vec<Vec<i32>>>,
+    /// Character Error Rate vs. a ground-truth reference, if one was evaluated
+    pub cer: Option<f64>,
+    /// Word Error Rate vs. a ground-truth reference, if one was evaluated
+    pub wer: Option<f64>,
+    /// Where this text came from: `"embedded"` (PDF text layer) or `"ocr"` (rasterized + recognized)
+    pub source: Option<String>,
+}
+
+/// A single compiled normalization rule, applied via `replace_all` in declared order
+enum NormalizeRule {
+    Regex(Regex, String),
+    Exact(String, String),
+}
+
+/// Ordered set of text cleanups (collapse whitespace, fix l/1 or O/0 confusions, strip
+/// control chars, join hyphenated line breaks, ...) applied to `OcrOutput.text` before formatting
+pub struct NormalizePipeline {
+    rules: Vec<NormalizeRule>,
+}
+
+impl NormalizePipeline {
+    /// Compile a pipeline from config-loaded rules, in declared order
+    pub fn compile(rules: &[NormalizeRuleConfig]) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(rules.len());
+
+        for rule in rules {
+            if rule.exact {
+                compiled.push(NormalizeRule::Exact(rule.pattern.clone(), rule.replace.clone()));
+            } else {
+                let regex = Regex::new(&rule.pattern)
+                    .map_err(|e| anyhow::anyhow!("Invalid normalize rule pattern '{}': {}", rule.pattern, e))?;
+                compiled.push(NormalizeRule::Regex(regex, rule.replace.clone()));
+            }
+        }
+
+        Ok(Self { rules: compiled })
+    }
+
+    /// Apply every rule in order, returning the cleaned text
+    pub fn apply(&self, text: &str) -> String {
+        let mut current = text.to_string();
+        for rule in &self.rules {
+            current = match rule {
+                NormalizeRule::Regex(regex, replace) => regex.replace_all(&current, replace.as_str()).into_owned(),
+                NormalizeRule::Exact(pattern, replace) => current.replace(pattern.as_str(), replace),
+            };
+        }
+        current
+    }
+}
+
+/// Pick the rule set a run should normalize with: a named profile's own `rules` take
+/// precedence over the top-level `[normalize]` default, so a profile can opt out of or
+/// override the default cleanup without disturbing other profiles
+///
+/// Returns `None` (no normalization) when neither the profile nor the top-level config
+/// defines any rules.
+pub fn resolve_pipeline(config: &Config, profile_name: Option<&str>) -> Result<Option<NormalizePipeline>> {
+    let profile_rules = profile_name
+        .and_then(|name| config.profiles.as_ref()?.get(name))
+        .and_then(|profile| profile.rules.as_ref());
+
+    let rules = match profile_rules {
+        Some(rules) => Some(rules),
+        None => config.normalize.as_ref().and_then(|n| n.rules.as_ref()),
+    };
+
+    match rules {
+        Some(rules) => Ok(Some(NormalizePipeline::compile(rules)?)),
+        None => Ok(None),
+    }
+}
+
+fn normalized_text(text: &str, pipeline: Option<&NormalizePipeline>) -> String {
+    match pipeline {
+        Some(pipeline) => pipeline.apply(text),
+        None => text.to_string(),
+    }
 }
 
 /// Format output as CSV
-pub fn format_csv(results: &[OcrOutput]) -> Result<String> {
-    let mut output = String::from("file,text,confidence,bbox_x1,bbox_y1,bbox_x2,bbox_y2\n");
-    
+pub fn format_csv(results: &[OcrOutput], pipeline: Option<&NormalizePipeline>) -> Result<String> {
+    let mut output = String::from("file,text,confidence,bbox_x1,bbox_y1,bbox_x2,bbox_y2,cer,wer\n");
+
     for result in results {
         let bbox_str = if let Some(bbox) = &result.bbox {
             if !bbox.is_empty() {
-                format!("{},{},{},{}", 
+                format!("{},{},{},{}",
                     bbox[0][0], bbox[0][1], bbox[2][0], bbox[2][1])
             } else {
                 ",,,,".to_string()
@@ -29,28 +110,33 @@ pub fn format_csv(results: &[OcrOutput]) -> Result<String> {
         } else {
             ",,,,".to_string()
         };
-        
-        let text_escaped = result.text.replace('"', "\"\"");
+
+        let cer_str = result.cer.map(|v| v.to_string()).unwrap_or_default();
+        let wer_str = result.wer.map(|v| v.to_string()).unwrap_or_default();
+
+        let text = normalized_text(&result.text, pipeline);
+        let text_escaped = text.replace('"', "\"\"");
         output.push_str(&format!(
-            "\"{}\",\"{}\",{},{}\n",
-            result.file, text_escaped, result.confidence, bbox_str
+            "\"{}\",\"{}\",{},{},{},{}\n",
+            result.file, text_escaped, result.confidence, bbox_str, cer_str, wer_str
         ));
     }
-    
+
     Ok(output)
 }
 
 /// Format output as XML
-pub fn format_xml(results: &[OcrOutput]) -> Result<String> {
+pub fn format_xml(results: &[OcrOutput], pipeline: Option<&NormalizePipeline>) -> Result<String> {
     let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ocr_results>\n");
-    
+
     for result in results {
         output.push_str("  <result>\n");
         output.push_str(&format!("    <file>{}</file>\n", result.file));
-        output.push_str(&format!("    <text>{}</text>\n", 
-            html_escape::encode_text(&result.text)));
+        let text = normalized_text(&result.text, pipeline);
+        output.push_str(&format!("    <text>{}</text>\n",
+            html_escape::encode_text(&text)));
         output.push_str(&format!("    <confidence>{}</confidence>\n", result.confidence));
-        
+
         if let Some(bbox) = &result.bbox {
             output.push_str("    <bbox>\n");
             for (i, point) in bbox.iter().enumerate() {
@@ -61,24 +147,25 @@ pub fn format_xml(results: &[OcrOutput]) -> Result<String> {
             }
             output.push_str("    </bbox>\n");
         }
-        
+
         output.push_str("  </result>\n");
     }
-    
+
     output.push_str("</ocr_results>\n");
     Ok(output)
 }
 
 /// Format output as Markdown
-pub fn format_markdown(results: &[OcrOutput]) -> Result<String> {
+pub fn format_markdown(results: &[OcrOutput], pipeline: Option<&NormalizePipeline>) -> Result<String> {
     let mut output = String::from("# OCR Results\n\n");
-    
+
     for (i, result) in results.iter().enumerate() {
         output.push_str(&format!("## Result {}\n\n", i + 1));
         output.push_str(&format!("**File:** `{}`\n\n", result.file));
-        output.push_str(&format!("**Text:**\n```\n{}\n```\n\n", result.text));
+        let text = normalized_text(&result.text, pipeline);
+        output.push_str(&format!("**Text:**\n```\n{}\n```\n\n", text));
         output.push_str(&format!("**Confidence:** {:.2}%\n\n", result.confidence * 100.0));
-        
+
         if let Some(bbox) = &result.bbox {
             output.push_str("**Bounding Box:**\n");
             for (i, point) in bbox.iter().enumerate() {
@@ -86,16 +173,29 @@ pub fn format_markdown(results: &[OcrOutput]) -> Result<String> {
             }
             output.push_str("\n");
         }
-        
+
         output.push_str("---\n\n");
     }
-    
+
     Ok(output)
 }
 
 /// Format output as JSON (default)
-pub fn format_json(results: &[OcrOutput]) -> Result<String> {
-    serde_json::to_string_pretty(results)
+pub fn format_json(results: &[OcrOutput], pipeline: Option<&NormalizePipeline>) -> Result<String> {
+    let normalized: Vec<OcrOutput> = results
+        .iter()
+        .map(|r| OcrOutput {
+            file: r.file.clone(),
+            text: normalized_text(&r.text, pipeline),
+            confidence: r.confidence,
+            bbox: r.bbox.clone(),
+            cer: r.cer,
+            wer: r.wer,
+            source: r.source.clone(),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&normalized)
         .map_err(|e| anyhow::anyhow!("JSON formatting failed: {}", e))
 }
 
@@ -116,9 +216,12 @@ mod tests {
             text: "Hello".to_string(),
             confidence: 0.95,
             bbox: None,
+            cer: None,
+            wer: None,
+            source: None,
         }];
-        
-        let json = format_json(&results).unwrap();
+
+        let json = format_json(&results, None).unwrap();
         assert!(json.contains("Hello"));
         assert!(json.contains("test.jpg"));
     }
@@ -130,10 +233,59 @@ mod tests {
             text: "World".to_string(),
             confidence: 0.90,
             bbox: None,
+            cer: None,
+            wer: None,
+            source: None,
         }];
-        
-        let csv = format_csv(&results).unwrap();
+
+        let csv = format_csv(&results, None).unwrap();
         assert!(csv.contains("file,text,confidence"));
         assert!(csv.contains("World"));
     }
+
+    #[test]
+    fn test_resolve_pipeline_profile_overrides_default() {
+        use crate::config::{NormalizeConfig, ProfileConfig};
+        use std::collections::HashMap;
+
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "fast".to_string(),
+            ProfileConfig {
+                languages: None,
+                gpu: None,
+                output: None,
+                detail: None,
+                rules: Some(vec![NormalizeRuleConfig { pattern: "a".to_string(), replace: "b".to_string(), exact: true }]),
+            },
+        );
+
+        let config = Config {
+            default: None,
+            server: None,
+            batch: None,
+            normalize: Some(NormalizeConfig {
+                rules: Some(vec![NormalizeRuleConfig { pattern: "x".to_string(), replace: "y".to_string(), exact: true }]),
+            }),
+            profiles: Some(profiles),
+        };
+
+        let profile_pipeline = resolve_pipeline(&config, Some("fast")).unwrap().unwrap();
+        assert_eq!(profile_pipeline.apply("a"), "b");
+
+        let default_pipeline = resolve_pipeline(&config, None).unwrap().unwrap();
+        assert_eq!(default_pipeline.apply("x"), "y");
+
+        assert!(resolve_pipeline(&Config::default(), None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_normalize_pipeline_regex_and_exact() {
+        let rules = vec![
+            NormalizeRuleConfig { pattern: r"\s+".to_string(), replace: " ".to_string(), exact: false },
+            NormalizeRuleConfig { pattern: "0".to_string(), replace: "O".to_string(), exact: true },
+        ];
+        let pipeline = NormalizePipeline::compile(&rules).unwrap();
+        assert_eq!(pipeline.apply("hell0   world"), "hellO world");
+    }
 }